@@ -28,6 +28,7 @@ pub struct Universe {
     width: u32,
     height: u32,
     cells: Vec<Cell>,
+    gpu: Option<GpuSimState>,
 }
 #[wasm_bindgen]
 impl Universe {
@@ -102,6 +103,7 @@ impl Universe {
             width,
             height,
             cells,
+            gpu: None,
         }
     }
 
@@ -120,6 +122,41 @@ impl Universe {
     pub fn cells(&self) -> *const Cell {
         self.cells.as_ptr()
     }
+
+    /// Flip a single cell between `Alive`/`Dead`, e.g. in response to a
+    /// pointer click translated to grid coordinates. Out-of-range
+    /// `row`/`column` (reachable directly from JS) is a no-op rather than a
+    /// panic.
+    pub fn toggle_cell(&mut self, row: u32, column: u32) {
+        if row >= self.height || column >= self.width {
+            return;
+        }
+        let idx = self.get_index(row, column);
+        self.cells[idx] = if self.cells[idx] == Cell::Alive {
+            Cell::Dead
+        } else {
+            Cell::Alive
+        };
+    }
+
+    /// Stamp a known pattern (glider, pulsar, ...) at `(row, column)`.
+    /// `cells` is a flat, row-major `pattern_width`-wide grid of 0/1 bytes;
+    /// each live cell wraps into the universe the same way `tick` does. A
+    /// `pattern_width` of 0 (reachable directly from JS) is a no-op rather
+    /// than a division-by-zero panic.
+    pub fn insert_pattern(&mut self, row: u32, column: u32, cells: &[u8], pattern_width: u32) {
+        if pattern_width == 0 {
+            return;
+        }
+        for (i, &value) in cells.iter().enumerate() {
+            let delta_row = i as u32 / pattern_width;
+            let delta_col = i as u32 % pattern_width;
+            let target_row = (row + delta_row) % self.height;
+            let target_col = (column + delta_col) % self.width;
+            let idx = self.get_index(target_row, target_col);
+            self.cells[idx] = if value != 0 { Cell::Alive } else { Cell::Dead };
+        }
+    }
 }
 
 use std::fmt;
@@ -138,6 +175,270 @@ impl fmt::Display for Universe {
     }
 }
 
+/// Ping-pong GPU state for [`tick_gpu`]: the board lives as a texture, one
+/// generation is advanced by rendering a full-screen quad into the other
+/// texture's framebuffer, and `current` tracks which texture holds the
+/// latest generation.
+#[derive(Debug)]
+struct GpuSimState {
+    textures: [web_sys::WebGlTexture; 2],
+    framebuffers: [web_sys::WebGlFramebuffer; 2],
+    current: usize,
+    program: WebGlProgram,
+    quad_buffer: WebGlBuffer,
+}
+
+const GPU_TICK_VERT_SHADER: &str = r##"#version 300 es
+
+    in vec2 position;
+    out vec2 v_uv;
+
+    void main() {
+        v_uv = position * 0.5 + 0.5;
+        gl_Position = vec4(position, 0.0, 1.0);
+    }
+    "##;
+
+const GPU_TICK_FRAG_SHADER: &str = r##"#version 300 es
+    precision highp float;
+
+    uniform sampler2D u_state;
+    uniform vec2 u_texel;
+
+    in vec2 v_uv;
+    out vec4 outColor;
+
+    float cell_at(vec2 offset) {
+        return texture(u_state, v_uv + offset * u_texel).r > 0.5 ? 1.0 : 0.0;
+    }
+
+    void main() {
+        float alive = cell_at(vec2(0.0, 0.0));
+        float neighbors =
+            cell_at(vec2(-1.0, -1.0)) + cell_at(vec2(0.0, -1.0)) + cell_at(vec2(1.0, -1.0)) +
+            cell_at(vec2(-1.0, 0.0))                            + cell_at(vec2(1.0, 0.0)) +
+            cell_at(vec2(-1.0, 1.0)) + cell_at(vec2(0.0, 1.0)) + cell_at(vec2(1.0, 1.0));
+
+        float next = 0.0;
+        if (alive > 0.5) {
+            next = (neighbors == 2.0 || neighbors == 3.0) ? 1.0 : 0.0;
+        } else {
+            next = (neighbors == 3.0) ? 1.0 : 0.0;
+        }
+        outColor = vec4(next, next, next, 1.0);
+    }
+    "##;
+
+fn create_state_texture(
+    context: &WebGl2RenderingContext,
+    width: i32,
+    height: i32,
+    initial: Option<&[u8]>,
+) -> Result<web_sys::WebGlTexture, JsValue> {
+    let texture = context.create_texture().ok_or("Failed to create texture")?;
+    context.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture));
+    context.tex_parameteri(
+        WebGl2RenderingContext::TEXTURE_2D,
+        WebGl2RenderingContext::TEXTURE_WRAP_S,
+        WebGl2RenderingContext::REPEAT as i32,
+    );
+    context.tex_parameteri(
+        WebGl2RenderingContext::TEXTURE_2D,
+        WebGl2RenderingContext::TEXTURE_WRAP_T,
+        WebGl2RenderingContext::REPEAT as i32,
+    );
+    context.tex_parameteri(
+        WebGl2RenderingContext::TEXTURE_2D,
+        WebGl2RenderingContext::TEXTURE_MIN_FILTER,
+        WebGl2RenderingContext::NEAREST as i32,
+    );
+    context.tex_parameteri(
+        WebGl2RenderingContext::TEXTURE_2D,
+        WebGl2RenderingContext::TEXTURE_MAG_FILTER,
+        WebGl2RenderingContext::NEAREST as i32,
+    );
+    context
+        .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            WebGl2RenderingContext::TEXTURE_2D,
+            0,
+            WebGl2RenderingContext::RGBA as i32,
+            width,
+            height,
+            0,
+            WebGl2RenderingContext::RGBA,
+            WebGl2RenderingContext::UNSIGNED_BYTE,
+            initial,
+        )
+        .map_err(|_| JsValue::from_str("Failed to upload texture data"))?;
+    Ok(texture)
+}
+
+fn create_state_framebuffer(
+    context: &WebGl2RenderingContext,
+    texture: &web_sys::WebGlTexture,
+) -> Result<web_sys::WebGlFramebuffer, JsValue> {
+    let framebuffer = context.create_framebuffer().ok_or("Failed to create framebuffer")?;
+    context.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, Some(&framebuffer));
+    context.framebuffer_texture_2d(
+        WebGl2RenderingContext::FRAMEBUFFER,
+        WebGl2RenderingContext::COLOR_ATTACHMENT0,
+        WebGl2RenderingContext::TEXTURE_2D,
+        Some(texture),
+        0,
+    );
+    Ok(framebuffer)
+}
+
+/// Upload `universe`'s current cells to a ping-pong pair of GPU textures and
+/// compile the simulation shader, so subsequent generations can be advanced
+/// with [`tick_gpu`] instead of the CPU scan in [`Universe::tick`].
+#[wasm_bindgen]
+pub fn setup_gpu_simulation(
+    universe: &mut Universe,
+    context: &WebGl2RenderingContext,
+) -> Result<(), JsValue> {
+    let width = universe.width as i32;
+    let height = universe.height as i32;
+
+    let mut initial = vec![0u8; (width * height * 4) as usize];
+    for (i, cell) in universe.cells.iter().enumerate() {
+        let value = if *cell == Cell::Alive { 255 } else { 0 };
+        initial[i * 4] = value;
+        initial[i * 4 + 1] = value;
+        initial[i * 4 + 2] = value;
+        initial[i * 4 + 3] = 255;
+    }
+
+    let textures = [
+        create_state_texture(context, width, height, Some(&initial))?,
+        create_state_texture(context, width, height, None)?,
+    ];
+    let framebuffers = [
+        create_state_framebuffer(context, &textures[0])?,
+        create_state_framebuffer(context, &textures[1])?,
+    ];
+    context.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+
+    let vert_shader = compile_shader(
+        context,
+        WebGl2RenderingContext::VERTEX_SHADER,
+        GPU_TICK_VERT_SHADER,
+    )
+    .map_err(|e| JsValue::from_str(&e))?;
+    let frag_shader = compile_shader(
+        context,
+        WebGl2RenderingContext::FRAGMENT_SHADER,
+        GPU_TICK_FRAG_SHADER,
+    )
+    .map_err(|e| JsValue::from_str(&e))?;
+    let program = link_program(context, &vert_shader, &frag_shader).map_err(|e| JsValue::from_str(&e))?;
+
+    let quad_buffer = context.create_buffer().ok_or("Failed to create quad buffer")?;
+    context.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&quad_buffer));
+    let quad_vertices: [f32; 12] = [
+        -1.0, -1.0, 1.0, -1.0, 1.0, 1.0, -1.0, -1.0, 1.0, 1.0, -1.0, 1.0,
+    ];
+    unsafe {
+        let view = js_sys::Float32Array::view(&quad_vertices);
+        context.buffer_data_with_array_buffer_view(
+            WebGl2RenderingContext::ARRAY_BUFFER,
+            &view,
+            WebGl2RenderingContext::STATIC_DRAW,
+        );
+    }
+
+    universe.gpu = Some(GpuSimState {
+        textures,
+        framebuffers,
+        current: 0,
+        program,
+        quad_buffer,
+    });
+    Ok(())
+}
+
+/// Advance `universe` by one generation entirely on the GPU: render a
+/// full-screen quad into the framebuffer for the "next" texture, sampling
+/// each texel's 8 neighbors from the "current" texture, then swap. Call
+/// [`sync_cells_from_gpu`] when `self.cells`/`render()` need to reflect the
+/// new state.
+#[wasm_bindgen]
+pub fn tick_gpu(universe: &mut Universe, context: &WebGl2RenderingContext) -> Result<(), JsValue> {
+    let width = universe.width as i32;
+    let height = universe.height as i32;
+    let gpu = universe
+        .gpu
+        .as_mut()
+        .ok_or_else(|| JsValue::from_str("call setup_gpu_simulation before tick_gpu"))?;
+
+    // The on-screen canvas is sized independently of the universe, so save
+    // the caller's viewport and restore it once the simulation pass is done
+    // instead of leaving the GL state clipped to the (smaller) board size.
+    let prev_viewport = js_sys::Int32Array::new(&context.get_parameter(WebGl2RenderingContext::VIEWPORT)?)
+        .to_vec();
+
+    let next = 1 - gpu.current;
+    context.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, Some(&gpu.framebuffers[next]));
+    context.viewport(0, 0, width, height);
+
+    context.use_program(Some(&gpu.program));
+    context.active_texture(WebGl2RenderingContext::TEXTURE0);
+    context.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&gpu.textures[gpu.current]));
+    let state_location = context.get_uniform_location(&gpu.program, "u_state");
+    context.uniform1i(state_location.as_ref(), 0);
+    let texel_location = context.get_uniform_location(&gpu.program, "u_texel");
+    context.uniform2f(texel_location.as_ref(), 1.0 / width as f32, 1.0 / height as f32);
+
+    context.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&gpu.quad_buffer));
+    let position_location = context.get_attrib_location(&gpu.program, "position") as u32;
+    context.vertex_attrib_pointer_with_i32(position_location, 2, WebGl2RenderingContext::FLOAT, false, 0, 0);
+    context.enable_vertex_attrib_array(position_location);
+
+    context.draw_arrays(WebGl2RenderingContext::TRIANGLES, 0, 6);
+    context.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+    context.viewport(prev_viewport[0], prev_viewport[1], prev_viewport[2], prev_viewport[3]);
+
+    gpu.current = next;
+    Ok(())
+}
+
+/// Read the current GPU texture back into `universe.cells` so `render()`
+/// and the existing CPU-side tests keep working against GPU-simulated
+/// generations.
+#[wasm_bindgen]
+pub fn sync_cells_from_gpu(universe: &mut Universe, context: &WebGl2RenderingContext) -> Result<(), JsValue> {
+    let width = universe.width as i32;
+    let height = universe.height as i32;
+    let gpu = universe
+        .gpu
+        .as_ref()
+        .ok_or_else(|| JsValue::from_str("call setup_gpu_simulation before sync_cells_from_gpu"))?;
+
+    context.bind_framebuffer(
+        WebGl2RenderingContext::FRAMEBUFFER,
+        Some(&gpu.framebuffers[gpu.current]),
+    );
+    let mut pixels = vec![0u8; (width * height * 4) as usize];
+    context
+        .read_pixels_with_opt_u8_array(
+            0,
+            0,
+            width,
+            height,
+            WebGl2RenderingContext::RGBA,
+            WebGl2RenderingContext::UNSIGNED_BYTE,
+            Some(&mut pixels),
+        )
+        .map_err(|_| JsValue::from_str("Failed to read back simulation texture"))?;
+    context.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+
+    universe.cells = pixels
+        .chunks(4)
+        .map(|px| if px[0] > 127 { Cell::Alive } else { Cell::Dead })
+        .collect();
+    Ok(())
+}
+
 #[wasm_bindgen]
 pub fn start() -> Result<(), JsValue> {
     let document = web_sys::window().unwrap().document().unwrap();
@@ -146,11 +447,126 @@ pub fn start() -> Result<(), JsValue> {
     Ok(())
 }
 
-use web_sys::{WebGl2RenderingContext, WebGlProgram, WebGlShader};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use web_sys::MouseEvent;
+
+/// Convert a mouse event's client coordinates to a grid `(row, column)`
+/// using the `(size + 1)` cell pitch already used by `draw`/`draw_grid`.
+/// Rows are counted up from the canvas's bottom edge, since `draw`'s
+/// orthographic projection maps y = 0 to the bottom of the canvas.
+fn event_to_cell(canvas: &web_sys::HtmlCanvasElement, event: &MouseEvent, size: f32) -> (u32, u32) {
+    let rect = canvas.get_bounding_client_rect();
+    let pitch = (size + 1.0) as f64;
+    let x = event.client_x() as f64 - rect.left();
+    let y = rect.bottom() - event.client_y() as f64;
+    ((y / pitch) as u32, (x / pitch) as u32)
+}
+
+/// Attach `mousedown`/`mousemove` listeners to the `#gl` canvas so clicking
+/// toggles a cell and dragging paints cells alive, redrawing after each
+/// edit. Takes ownership of `universe` so the closures can keep mutating it
+/// between events.
+#[wasm_bindgen]
+pub fn setup_pointer_controls(universe: Universe) -> Result<(), JsValue> {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let canvas = document
+        .get_element_by_id("gl")
+        .unwrap()
+        .dyn_into::<web_sys::HtmlCanvasElement>()?;
+
+    let universe = Rc::new(RefCell::new(universe));
+    let dragging = Rc::new(RefCell::new(false));
+    let size = 5f32;
+
+    {
+        let universe = universe.clone();
+        let dragging = dragging.clone();
+        let canvas = canvas.clone();
+        let closure = Closure::<dyn FnMut(MouseEvent)>::new(move |event: MouseEvent| {
+            *dragging.borrow_mut() = true;
+            let (row, column) = event_to_cell(&canvas, &event, size);
+            let mut universe = universe.borrow_mut();
+            if row < universe.height() && column < universe.width() {
+                universe.toggle_cell(row, column);
+            }
+            let _ = draw_universe(&universe);
+        });
+        canvas.add_event_listener_with_callback("mousedown", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    {
+        let universe = universe.clone();
+        let dragging = dragging.clone();
+        let canvas = canvas.clone();
+        let closure = Closure::<dyn FnMut(MouseEvent)>::new(move |event: MouseEvent| {
+            if !*dragging.borrow() {
+                return;
+            }
+            let (row, column) = event_to_cell(&canvas, &event, size);
+            let mut universe = universe.borrow_mut();
+            if row < universe.height() && column < universe.width() {
+                let idx = universe.get_index(row, column);
+                universe.cells[idx] = Cell::Alive;
+            }
+            let _ = draw_universe(&universe);
+        });
+        canvas.add_event_listener_with_callback("mousemove", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    {
+        let dragging = dragging.clone();
+        let closure = Closure::<dyn FnMut(MouseEvent)>::new(move |_event: MouseEvent| {
+            *dragging.borrow_mut() = false;
+        });
+        web_sys::window()
+            .unwrap()
+            .add_event_listener_with_callback("mouseup", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    Ok(())
+}
+use web_sys::{
+    OffscreenCanvas, WebGl2RenderingContext, WebGlBuffer, WebGlProgram, WebGlShader,
+    WebGlVertexArrayObject,
+};
+
+/// Where a WebGL2 context comes from: the main-thread `#gl` canvas, or an
+/// `OffscreenCanvas` transferred into a Web Worker. Factored out of
+/// `render_gl`/`draw_universe` so both entry points share one place that
+/// knows how to ask each canvas kind for its context.
+enum CanvasSource {
+    OnScreen(web_sys::HtmlCanvasElement),
+    Offscreen(OffscreenCanvas),
+}
 
+impl CanvasSource {
+    fn webgl2_context(&self) -> Result<WebGl2RenderingContext, JsValue> {
+        let context = match self {
+            CanvasSource::OnScreen(canvas) => canvas.get_context("webgl2")?,
+            CanvasSource::Offscreen(canvas) => canvas.get_context("webgl2")?,
+        };
+        context
+            .ok_or("webgl2 is not supported by this canvas")?
+            .dyn_into::<WebGl2RenderingContext>()
+    }
+}
+
+// `#[wasm_bindgen(start)]` runs on every module instantiation, including in
+// a Worker loading this module to call `draw_universe_offscreen` — and
+// `web_sys::window()` is `None` there, so the on-screen demo setup below must
+// be skipped rather than unwrapped.
 #[wasm_bindgen(start)]
 fn render_gl() -> Result<(), JsValue> {
-    let document = web_sys::window().unwrap().document().unwrap();
+    let Some(window) = web_sys::window() else {
+        return Ok(());
+    };
+    let document = window.document().unwrap();
     let canvas = document.get_element_by_id("gl").unwrap();
     let canvas: web_sys::HtmlCanvasElement = canvas.dyn_into::<web_sys::HtmlCanvasElement>()?;
 
@@ -159,10 +575,7 @@ fn render_gl() -> Result<(), JsValue> {
     canvas.set_height(dim);
     canvas.set_width(dim);
 
-    let context = canvas
-        .get_context("webgl2")?
-        .unwrap()
-        .dyn_into::<WebGl2RenderingContext>()?;
+    let context = CanvasSource::OnScreen(canvas).webgl2_context()?;
     let vert_shader = compile_shader(
         &context,
         WebGl2RenderingContext::VERTEX_SHADER,
@@ -263,6 +676,12 @@ fn render_gl() -> Result<(), JsValue> {
     Ok(())
 }
 
+thread_local! {
+    // Cached across ticks so the GL program/VAO/buffer are compiled and
+    // allocated once rather than recreated on every frame.
+    static RENDERER: RefCell<Option<Renderer>> = RefCell::new(None);
+}
+
 #[wasm_bindgen]
 pub fn draw_universe(universe: &Universe) -> Result<(), JsValue> {
     // web_sys::console::log_1(&format!("{:?}", universe).into());
@@ -270,60 +689,97 @@ pub fn draw_universe(universe: &Universe) -> Result<(), JsValue> {
     let canvas = document.get_element_by_id("gl").unwrap();
     let canvas: web_sys::HtmlCanvasElement = canvas.dyn_into::<web_sys::HtmlCanvasElement>()?;
 
-    let context = canvas
-        .get_context("webgl2")?
-        .unwrap()
-        .dyn_into::<WebGl2RenderingContext>()?;
+    let context = CanvasSource::OnScreen(canvas).webgl2_context()?;
 
-    draw(universe, &context);
+    render_with_cached_renderer(universe, context)
+}
 
-    Ok(())
+/// Worker-side counterpart to `draw_universe`: takes an `OffscreenCanvas`
+/// transferred from the main thread instead of looking up `#gl`, so the
+/// simulation can tick and render off the main thread without janking the
+/// page. Requires the `OffscreenCanvas` web-sys feature.
+#[wasm_bindgen]
+pub fn draw_universe_offscreen(universe: &Universe, canvas: OffscreenCanvas) -> Result<(), JsValue> {
+    let context = CanvasSource::Offscreen(canvas).webgl2_context()?;
+
+    render_with_cached_renderer(universe, context)
 }
 
-fn draw(universe: &Universe, context: &WebGl2RenderingContext) {
-    context.clear_color(1.0, 1.0, 1.0, 1.0);
+fn render_with_cached_renderer(universe: &Universe, context: WebGl2RenderingContext) -> Result<(), JsValue> {
+    RENDERER.with(|cell| -> Result<(), JsValue> {
+        let mut slot = cell.borrow_mut();
+        if slot.is_none() {
+            let mut renderer = Renderer::new(context)?;
+            renderer.register_shader(DEFAULT_SHADER, DEFAULT_VERT_SHADER, DEFAULT_FRAG_SHADER)?;
+            renderer.set_uniform_mat4(DEFAULT_SHADER, "model", &orthographic_matrix(BOARD_DIM))?;
+            *slot = Some(renderer);
+        }
+        draw(universe, slot.as_ref().unwrap());
+        Ok(())
+    })
+}
 
+fn draw(universe: &Universe, renderer: &Renderer) {
+    let context = renderer.context();
+    context.clear_color(1.0, 1.0, 1.0, 1.0);
     context.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
-    draw_grid(universe, context);
-    let size = 5f32;
 
+    draw_grid(universe, renderer);
+
+    let size = 5f32;
     let gray = 0.1f32;
+    let mut vertices = Vec::new();
     for row in 0..universe.height() {
         for col in 0..universe.width() {
             let idx = universe.get_index(row, col);
             let cell = universe.cells[idx];
+            if cell != Cell::Alive {
+                continue;
+            }
             let offset_x = (size + 1f32) * col as f32 + 1f32;
             let offset_y = (size + 1f32) * row as f32 + 1f32;
-            if cell == Cell::Alive {
-                let vertices = [
-                    offset_x,
-                    offset_y,
-                    gray,
-                    offset_x,
-                    offset_y + size,
-                    gray,
-                    offset_x + size,
-                    offset_y + size,
-                    gray,
-                    offset_x + size,
-                    offset_y,
-                    gray,
-                ];
-                draw_square(context, &vertices);
-            }
+            // Two triangles per cell instead of a per-cell TRIANGLE_FAN draw
+            // call, so the whole board becomes one draw_arrays(TRIANGLES).
+            vertices.extend_from_slice(&[
+                offset_x,
+                offset_y,
+                gray,
+                offset_x,
+                offset_y + size,
+                gray,
+                offset_x + size,
+                offset_y + size,
+                gray,
+                offset_x,
+                offset_y,
+                gray,
+                offset_x + size,
+                offset_y + size,
+                gray,
+                offset_x + size,
+                offset_y,
+                gray,
+            ]);
         }
     }
+    if !vertices.is_empty() {
+        let _ = renderer.render(&RenderItem {
+            vertices,
+            shader_name: DEFAULT_SHADER.to_string(),
+            uniforms: None,
+            primitive: WebGl2RenderingContext::TRIANGLES,
+        });
+    }
 }
 
-fn draw_grid(universe: &Universe, context: &WebGl2RenderingContext) {
-    // let size = 2f32 / universe.height() as f32;
-    // let size = universe.height() as f32 / 4f32;
+fn draw_grid(universe: &Universe, renderer: &Renderer) {
     let size = 5f32;
     let grid_length = 385f32;
     let gray = 0.6f32;
+    let mut vertices = Vec::new();
     for row in 0..=universe.height() {
         let offset = (size + 1f32) * row as f32;
-        let vertices = [
+        vertices.extend_from_slice(&[
             offset,
             0f32,
             gray,
@@ -336,35 +792,237 @@ fn draw_grid(universe: &Universe, context: &WebGl2RenderingContext) {
             grid_length,
             offset,
             gray,
-        ];
-        draw_line(context, &vertices);
+        ]);
     }
+    let _ = renderer.render(&RenderItem {
+        vertices,
+        shader_name: DEFAULT_SHADER.to_string(),
+        uniforms: None,
+        primitive: WebGl2RenderingContext::LINES,
+    });
 }
 
-fn draw_line(context: &WebGl2RenderingContext, vertices: &[f32]) {
-    unsafe {
-        let positions_array_buf_view = js_sys::Float32Array::view(vertices);
-        context.buffer_data_with_array_buffer_view(
-            WebGl2RenderingContext::ARRAY_BUFFER,
-            &positions_array_buf_view,
-            WebGl2RenderingContext::STATIC_DRAW,
+/// A typed uniform value, dispatched to the matching `uniform*` call when a
+/// [`RenderItem`] is submitted to a [`Renderer`].
+#[derive(Clone, Debug)]
+pub enum Uniform {
+    Vec4([f32; 4]),
+    Vec3([f32; 3]),
+    Vec2([f32; 2]),
+    Float(f32),
+}
+
+/// One batch of geometry to draw with a named, already-registered shader.
+pub struct RenderItem {
+    pub vertices: Vec<f32>,
+    pub shader_name: String,
+    pub uniforms: Option<HashMap<String, Uniform>>,
+    /// `WebGl2RenderingContext::{TRIANGLES, TRIANGLE_FAN, LINES, ...}`.
+    pub primitive: u32,
+}
+
+/// Name of the shared shader `draw`/`draw_grid` render with; registered once
+/// by `render_with_cached_renderer`.
+const DEFAULT_SHADER: &str = "default";
+
+const DEFAULT_VERT_SHADER: &str = r##"#version 300 es
+
+in vec2 position;
+in float color;
+uniform mat4 model;
+
+out float outColor;
+
+void main() {
+    gl_Position = model * vec4(position, 0, 1);
+    outColor = color;
+}
+"##;
+
+const DEFAULT_FRAG_SHADER: &str = r##"#version 300 es
+
+precision highp float;
+in float outColor;
+out vec4 diffuseColor;
+
+void main() {
+    diffuseColor = vec4(outColor, outColor, outColor, 1);
+}
+"##;
+
+/// Board dimension in pixels that `draw`/`draw_grid` lay their vertices out
+/// against: `64` cells at a `5 + 1` pixel pitch, plus one pixel for the final
+/// grid line.
+const BOARD_DIM: f32 = 64f32 * (5f32 + 1f32) + 1f32;
+
+/// Row-major orthographic projection (matches `uniform_matrix4fv`'s
+/// `transpose = true`) mapping a `dim x dim` pixel board onto clip space.
+fn orthographic_matrix(dim: f32) -> [f32; 16] {
+    let (left, bottom, far) = (0f32, 0f32, -1f32);
+    let (right, top, near) = (dim, dim, 2f32);
+    [
+        2f32 / (right - left),
+        0f32,
+        0f32,
+        -(right + left) / (right - left),
+        0f32,
+        2f32 / (top - bottom),
+        0f32,
+        -(top + bottom) / (top - bottom),
+        0f32,
+        0f32,
+        -2f32 / (far - near),
+        -(far + near) / (far - near),
+        0f32,
+        0f32,
+        0f32,
+        1f32,
+    ]
+}
+
+/// Holds the GL context, a cache of pre-compiled named shader programs, and
+/// the VAO/buffer every [`RenderItem`] is uploaded through. Register shaders
+/// once with [`Renderer::register_shader`], then submit [`RenderItem`]s via
+/// [`Renderer::render`].
+pub struct Renderer {
+    context: WebGl2RenderingContext,
+    programs: HashMap<String, WebGlProgram>,
+    vao: WebGlVertexArrayObject,
+    buffer: WebGlBuffer,
+}
+
+impl Renderer {
+    pub fn new(context: WebGl2RenderingContext) -> Result<Renderer, JsValue> {
+        let vao = context
+            .create_vertex_array()
+            .ok_or("Could not create vertex array object")?;
+        let buffer = context.create_buffer().ok_or("Failed to create buffer")?;
+        Ok(Renderer {
+            context,
+            programs: HashMap::new(),
+            vao,
+            buffer,
+        })
+    }
+
+    pub fn context(&self) -> &WebGl2RenderingContext {
+        &self.context
+    }
+
+    /// Compile and link `vert_source`/`frag_source` and cache the resulting
+    /// program under `name` for later lookup by [`Renderer::render`].
+    pub fn register_shader(
+        &mut self,
+        name: &str,
+        vert_source: &str,
+        frag_source: &str,
+    ) -> Result<(), JsValue> {
+        let vert_shader = compile_shader(&self.context, WebGl2RenderingContext::VERTEX_SHADER, vert_source)
+            .map_err(|e| JsValue::from_str(&e))?;
+        let frag_shader = compile_shader(
+            &self.context,
+            WebGl2RenderingContext::FRAGMENT_SHADER,
+            frag_source,
+        )
+        .map_err(|e| JsValue::from_str(&e))?;
+        let program =
+            link_program(&self.context, &vert_shader, &frag_shader).map_err(|e| JsValue::from_str(&e))?;
+        self.programs.insert(name.to_string(), program);
+        Ok(())
+    }
+
+    /// Set a `mat4` uniform on an already-[`register_shader`](Self::register_shader)ed
+    /// program. Split out from [`Uniform`]/[`Self::render`] since `model` only needs
+    /// setting once per shader, not once per [`RenderItem`].
+    pub fn set_uniform_mat4(&self, shader_name: &str, name: &str, value: &[f32; 16]) -> Result<(), JsValue> {
+        let program = self
+            .programs
+            .get(shader_name)
+            .ok_or_else(|| JsValue::from_str(&format!("unknown shader: {}", shader_name)))?;
+        self.context.use_program(Some(program));
+        let location = self.context.get_uniform_location(program, name);
+        self.context
+            .uniform_matrix4fv_with_f32_array(location.as_ref(), true, value);
+        Ok(())
+    }
+
+    fn set_uniform(&self, program: &WebGlProgram, name: &str, value: &Uniform) {
+        let location = self.context.get_uniform_location(program, name);
+        match value {
+            Uniform::Vec4(v) => self.context.uniform4f(location.as_ref(), v[0], v[1], v[2], v[3]),
+            Uniform::Vec3(v) => self.context.uniform3f(location.as_ref(), v[0], v[1], v[2]),
+            Uniform::Vec2(v) => self.context.uniform2f(location.as_ref(), v[0], v[1]),
+            Uniform::Float(v) => self.context.uniform1f(location.as_ref(), *v),
+        }
+    }
+
+    /// Look up `item.shader_name`, bind it, point `position`/`color` at
+    /// `self.buffer` (`vertex_attrib_pointer` captures whichever buffer is
+    /// bound to `ARRAY_BUFFER` *right now*, not at draw time, so this has to
+    /// run on every call rather than once at setup), set any uniforms,
+    /// upload the vertex buffer, and issue a single draw call with
+    /// `item.primitive`.
+    pub fn render(&self, item: &RenderItem) -> Result<(), JsValue> {
+        let program = self
+            .programs
+            .get(&item.shader_name)
+            .ok_or_else(|| JsValue::from_str(&format!("unknown shader: {}", item.shader_name)))?;
+        self.context.use_program(Some(program));
+        self.context.bind_vertex_array(Some(&self.vao));
+        self.context
+            .bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&self.buffer));
+
+        let position_attribute_location = self.context.get_attrib_location(program, "position");
+        self.context.vertex_attrib_pointer_with_i32(
+            position_attribute_location as u32,
+            2,
+            WebGl2RenderingContext::FLOAT,
+            false,
+            12,
+            0,
         );
+        self.context
+            .enable_vertex_attrib_array(position_attribute_location as u32);
+
+        let color_attribute_location = self.context.get_attrib_location(program, "color");
+        self.context.vertex_attrib_pointer_with_i32(
+            color_attribute_location as u32,
+            1,
+            WebGl2RenderingContext::FLOAT,
+            false,
+            12,
+            8,
+        );
+        self.context
+            .enable_vertex_attrib_array(color_attribute_location as u32);
+
+        if let Some(uniforms) = &item.uniforms {
+            for (name, value) in uniforms.iter() {
+                self.set_uniform(program, name, value);
+            }
+        }
+
+        unsafe {
+            let positions_array_buf_view = js_sys::Float32Array::view(&item.vertices);
+            self.context.buffer_data_with_array_buffer_view(
+                WebGl2RenderingContext::ARRAY_BUFFER,
+                &positions_array_buf_view,
+                WebGl2RenderingContext::DYNAMIC_DRAW,
+            );
+        }
+        let vert_count = (item.vertices.len() / 3) as i32;
+        self.context.draw_arrays(item.primitive, 0, vert_count);
+
+        Ok(())
     }
-    let vert_count = (vertices.len() / 3) as i32;
-    context.draw_arrays(WebGl2RenderingContext::LINES, 0, vert_count);
 }
 
-fn draw_square(context: &WebGl2RenderingContext, vertices: &[f32]) {
-    unsafe {
-        let positions_array_buf_view = js_sys::Float32Array::view(vertices);
-        context.buffer_data_with_array_buffer_view(
-            WebGl2RenderingContext::ARRAY_BUFFER,
-            &positions_array_buf_view,
-            WebGl2RenderingContext::STATIC_DRAW,
-        );
+fn shader_type_name(shader_type: u32) -> &'static str {
+    match shader_type {
+        WebGl2RenderingContext::VERTEX_SHADER => "vertex",
+        WebGl2RenderingContext::FRAGMENT_SHADER => "fragment",
+        _ => "unknown",
     }
-    let vert_count = (vertices.len() / 3) as i32;
-    context.draw_arrays(WebGl2RenderingContext::TRIANGLE_FAN, 0, vert_count);
 }
 
 pub fn compile_shader(
@@ -378,6 +1036,14 @@ pub fn compile_shader(
     context.shader_source(&shader, source);
     context.compile_shader(&shader);
 
+    if let Some(log) = context.get_shader_info_log(&shader) {
+        if !log.is_empty() {
+            web_sys::console::log_1(
+                &format!("[{} shader] {}", shader_type_name(shader_type), log).into(),
+            );
+        }
+    }
+
     if context
         .get_shader_parameter(&shader, WebGl2RenderingContext::COMPILE_STATUS)
         .as_bool()
@@ -404,19 +1070,38 @@ pub fn link_program(
     context.attach_shader(&program, frag_shader);
     context.link_program(&program);
 
-    if context
+    if !context
         .get_program_parameter(&program, WebGl2RenderingContext::LINK_STATUS)
         .as_bool()
         .unwrap_or(false)
     {
-        Ok(program)
-    } else {
-        Err(context
+        return Err(context
             .get_program_info_log(&program)
-            .unwrap_or_else(|| String::from("Unknown error creating program object")))
+            .unwrap_or_else(|| String::from("Unknown error creating program object")));
+    }
+
+    // validate_program is only meaningful against full draw-time GL state
+    // (bound VAO/attributes/textures), which doesn't exist yet right after
+    // linking, so a false VALIDATE_STATUS here doesn't mean the program is
+    // actually broken. Log it like the other diagnostics instead of failing
+    // program creation on it.
+    context.validate_program(&program);
+    if let Some(log) = context.get_program_info_log(&program) {
+        if !log.is_empty() {
+            web_sys::console::log_1(&format!("[program] {}", log).into());
+        }
     }
+
+    Ok(program)
 }
 
+// `setup_gpu_simulation`/`tick_gpu`/`sync_cells_from_gpu` need a real
+// `WebGl2RenderingContext`, which only exists in a browser or a headless-GL
+// test harness (e.g. `wasm-bindgen-test` with a browser runner); plain
+// `cargo test` has neither, and this crate has no Cargo.toml to add the dev
+// dependency and `#[wasm_bindgen_test]` harness that would wire one up. The
+// CPU/GPU cell round trip is exercised manually against the on-screen demo
+// instead of here until that harness exists.
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -426,4 +1111,45 @@ mod tests {
         let result = add(2, 2);
         assert_eq!(result, 4);
     }
+
+    #[test]
+    fn toggle_cell_out_of_range_is_noop() {
+        let mut universe = Universe::new();
+        let before = universe.cells.clone();
+        universe.toggle_cell(universe.height(), 0);
+        universe.toggle_cell(0, universe.width());
+        assert_eq!(universe.cells, before);
+    }
+
+    #[test]
+    fn toggle_cell_flips_in_range_cell() {
+        let mut universe = Universe::new();
+        let idx = universe.get_index(0, 0);
+        let before = universe.cells[idx];
+        universe.toggle_cell(0, 0);
+        let after = universe.cells[idx];
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn insert_pattern_wraps_around_edges() {
+        let mut universe = Universe::new();
+        let row = universe.height() - 1;
+        let column = universe.width() - 1;
+        // 2x2 pattern placed at the bottom-right corner should wrap its
+        // second row/column back to index 0, same as `tick`'s neighbor wrap.
+        universe.insert_pattern(row, column, &[1, 0, 0, 1], 2);
+        assert_eq!(universe.cells[universe.get_index(row, column)], Cell::Alive);
+        assert_eq!(universe.cells[universe.get_index(row, 0)], Cell::Dead);
+        assert_eq!(universe.cells[universe.get_index(0, column)], Cell::Dead);
+        assert_eq!(universe.cells[universe.get_index(0, 0)], Cell::Alive);
+    }
+
+    #[test]
+    fn insert_pattern_zero_width_is_noop() {
+        let mut universe = Universe::new();
+        let before = universe.cells.clone();
+        universe.insert_pattern(0, 0, &[1, 1, 1, 1], 0);
+        assert_eq!(universe.cells, before);
+    }
 }